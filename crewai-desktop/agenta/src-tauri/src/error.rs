@@ -0,0 +1,75 @@
+use std::io::ErrorKind;
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use thiserror::Error;
+
+/// Errors surfaced by the Python backend bridge. Unlike a bare `String`, these
+/// let the UI distinguish "Python isn't installed" from "the backend crashed"
+/// and show an actionable message.
+#[derive(Debug, Error)]
+pub enum BackendError {
+    /// The Python interpreter could not be found on `PATH`.
+    #[error("Python interpreter not found: {0}")]
+    NotFound(String),
+
+    /// The OS refused to execute the interpreter.
+    #[error("Permission denied launching the backend: {0}")]
+    PermissionDenied(String),
+
+    /// Spawning the backend failed for some other reason, or killing it on
+    /// `stop` failed.
+    #[error("Failed to spawn or kill the backend: {0}")]
+    SpawnFailed(std::io::Error),
+
+    /// A command was issued while no backend was running.
+    #[error("Backend is not running")]
+    NotRunning,
+
+    /// The backend did not answer within the allotted time.
+    #[error("Timed out waiting for the backend")]
+    Timeout,
+
+    /// The backend process exited with a non-zero status.
+    #[error("Backend exited with status {0}")]
+    ExitStatus(std::process::ExitStatus),
+
+    /// A request or response could not be (de)serialized.
+    #[error("Protocol error: {0}")]
+    Protocol(#[from] serde_json::Error),
+}
+
+impl BackendError {
+    /// Classify a `Command::spawn` failure into the most specific variant so
+    /// the frontend can, e.g., prompt the user to install Python.
+    pub fn from_spawn(err: std::io::Error) -> Self {
+        match err.kind() {
+            ErrorKind::NotFound => BackendError::NotFound(err.to_string()),
+            ErrorKind::PermissionDenied => BackendError::PermissionDenied(err.to_string()),
+            _ => BackendError::SpawnFailed(err),
+        }
+    }
+
+    /// Stable machine-readable discriminant for the frontend to switch on.
+    fn kind(&self) -> &'static str {
+        match self {
+            BackendError::NotFound(_) => "not_found",
+            BackendError::PermissionDenied(_) => "permission_denied",
+            BackendError::SpawnFailed(_) => "spawn_failed",
+            BackendError::NotRunning => "not_running",
+            BackendError::Timeout => "timeout",
+            BackendError::ExitStatus(_) => "exit_status",
+            BackendError::Protocol(_) => "protocol",
+        }
+    }
+}
+
+// Serialize as `{ "kind", "message" }` so the webview gets a structured error
+// without exposing the non-serializable inner io/serde types.
+impl Serialize for BackendError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("BackendError", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
@@ -1,148 +1,158 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::process::{Command, Stdio};
-use std::sync::Mutex;
-use tauri::State;
-use serde::{Deserialize, Serialize};
-use std::io::{Write, BufRead};
-use std::path::PathBuf;
-
-#[derive(Default)]
-struct PythonProcess(Mutex<Option<std::process::Child>>);
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-enum CommandStatus {
-    Success,
-    Error,
+mod error;
+mod python;
+mod session;
+
+use error::BackendError;
+use python::{BackendStatus, StreamFrame};
+use serde::Serialize;
+use session::{BackendHandle, SessionId, SessionManager};
+use tauri::{AppHandle, Manager, State};
+
+/// Payload for the `backend://status` event, tagged with the session it
+/// describes so a frontend running several sessions can route it.
+#[derive(Debug, Clone, Serialize)]
+struct SessionStatus {
+    session: SessionId,
+    #[serde(flatten)]
+    status: BackendStatus,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct PythonMessage {
-    status: CommandStatus,
-    data: Option<serde_json::Value>,
-    error: Option<String>,
+/// Payload for the `backend://stream` event, tagged with its session.
+#[derive(Debug, Clone, Serialize)]
+struct SessionStream {
+    session: SessionId,
+    #[serde(flatten)]
+    frame: StreamFrame,
 }
 
-fn get_backend_path() -> PathBuf {
-    let mut path = std::env::current_dir().expect("Failed to get current directory");
-    path.push("src");
-    path.push("backend");
-    path.push("main.py");
-    path
-}
+/// Wire up a freshly started session: forward its status transitions and
+/// stream frames to the webview, and supervise it for crashes.
+fn launch_session(app: &AppHandle, id: SessionId, handle: BackendHandle) {
+    let status_app = app.clone();
+    let mut status_rx = handle.subscribe_status();
+    tauri::async_runtime::spawn(async move {
+        while let Ok(status) = status_rx.recv().await {
+            let _ = status_app.emit_all("backend://status", SessionStatus { session: id, status });
+        }
+    });
 
-#[tauri::command]
-async fn start_backend(python_process: State<'_, PythonProcess>) -> Result<String, String> {
-    let mut process = python_process.0.lock().map_err(|e| e.to_string())?;
-    
-    if process.is_some() {
-        return Ok("Backend already running".to_string());
-    }
+    let stream_app = app.clone();
+    let mut stream_rx = handle.subscribe_stream();
+    tauri::async_runtime::spawn(async move {
+        while let Ok(frame) = stream_rx.recv().await {
+            let _ = stream_app.emit_all("backend://stream", SessionStream { session: id, frame });
+        }
+    });
 
-    let python_path = std::env::var("PYTHON_PATH").unwrap_or_else(|_| "python".to_string());
-    let backend_path = get_backend_path();
+    // `start` already fired the first `Running`, but no listener was attached
+    // yet; replay it now that the status forwarder above is subscribed so the
+    // frontend sees the backend come up.
+    handle.announce_running();
 
-    let child = Command::new(&python_path)
-        .arg(backend_path)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start Python backend: {}", e))?;
+    tauri::async_runtime::spawn(handle.supervise());
+}
 
-    *process = Some(child);
-    Ok("Backend started successfully".to_string())
+#[tauri::command]
+async fn start_backend(
+    app: AppHandle,
+    sessions: State<'_, SessionManager>,
+) -> Result<SessionId, BackendError> {
+    let (id, handle) = sessions.create();
+    if let Err(e) = handle.start().await {
+        sessions.remove(id);
+        return Err(e);
+    }
+    launch_session(&app, id, handle);
+    Ok(id)
 }
 
 #[tauri::command]
-async fn stop_backend(python_process: State<'_, PythonProcess>) -> Result<String, String> {
-    let mut process = python_process.0.lock().map_err(|e| e.to_string())?;
-    
-    if let Some(mut child) = process.take() {
-        child.kill().map_err(|e| format!("Failed to kill Python process: {}", e))?;
-        child.wait().map_err(|e| format!("Failed to wait for Python process: {}", e))?;
-        Ok("Backend stopped successfully".to_string())
-    } else {
-        Ok("Backend was not running".to_string())
+async fn stop_backend(
+    session: SessionId,
+    sessions: State<'_, SessionManager>,
+) -> Result<String, BackendError> {
+    match sessions.remove(session) {
+        Some(handle) => {
+            handle.stop().await?;
+            Ok("Backend stopped successfully".to_string())
+        }
+        None => Ok("Backend was not running".to_string()),
     }
 }
 
 #[tauri::command]
 async fn send_command(
+    session: SessionId,
     command: String,
     args: String,
-    python_process: State<'_, PythonProcess>,
-) -> Result<String, String> {
-    let process = python_process.0.lock().map_err(|e| e.to_string())?;
-    
-    if let Some(child) = process.as_ref() {
-        let stdin = child.stdin.as_ref()
-            .ok_or_else(|| "Failed to get stdin handle".to_string())?;
-        let mut stdin = stdin.lock().map_err(|e| e.to_string())?;
-
-        let message = serde_json::json!({
-            "command": command,
-            "args": serde_json::from_str::<serde_json::Value>(&args)
-                .map_err(|e| format!("Failed to parse args: {}", e))?
-        });
-
-        stdin.write_all(message.to_string().as_bytes())
-            .map_err(|e| format!("Failed to write to Python process: {}", e))?;
-        stdin.write_all(b"\n")
-            .map_err(|e| format!("Failed to write newline: {}", e))?;
+    sessions: State<'_, SessionManager>,
+) -> Result<String, BackendError> {
+    let handle = sessions.get(session).ok_or(BackendError::NotRunning)?;
+    let args: serde_json::Value = serde_json::from_str(&args)?;
+    let response = handle.send_command(&command, args).await?;
+    Ok(serde_json::to_string(&response)?)
+}
 
-        // Read response with timeout
-        if let Some(stdout) = child.stdout.as_ref() {
-            let reader = std::io::BufReader::new(stdout);
-            let response = tokio::time::timeout(
-                std::time::Duration::from_secs(30),
-                tokio::task::spawn_blocking(move || {
-                    reader.lines()
-                        .next()
-                        .transpose()
-                        .map_err(|e| format!("Failed to read response: {}", e))
-                })
-            ).await
-                .map_err(|_| "Command timed out".to_string())?
-                .map_err(|e| format!("Task failed: {}", e))??
-                .ok_or_else(|| "No response from backend".to_string())?;
+#[tauri::command]
+async fn subscribe(
+    session: SessionId,
+    command: String,
+    args: String,
+    sessions: State<'_, SessionManager>,
+) -> Result<u64, BackendError> {
+    let handle = sessions.get(session).ok_or(BackendError::NotRunning)?;
+    let args: serde_json::Value = serde_json::from_str(&args)?;
+    handle.subscribe(&command, args).await
+}
 
-            Ok(response)
-        } else {
-            Err("Failed to get stdout handle".to_string())
-        }
-    } else {
-        Err("Backend not running".to_string())
-    }
+#[tauri::command]
+async fn cancel(
+    session: SessionId,
+    id: u64,
+    sessions: State<'_, SessionManager>,
+) -> Result<(), BackendError> {
+    let handle = sessions.get(session).ok_or(BackendError::NotRunning)?;
+    handle.cancel(id).await
 }
 
 #[tauri::command]
-async fn check_backend(python_process: State<'_, PythonProcess>) -> bool {
-    python_process.0.lock()
-        .map(|guard| guard.is_some())
+async fn check_backend(session: SessionId, sessions: State<'_, SessionManager>) -> bool {
+    sessions
+        .get(session)
+        .map(|handle| handle.is_ready())
         .unwrap_or(false)
 }
 
 fn main() {
     tauri::Builder::default()
-        .manage(PythonProcess::default())
+        .manage(SessionManager::new())
         .invoke_handler(tauri::generate_handler![
             start_backend,
             stop_backend,
             send_command,
+            subscribe,
+            cancel,
             check_backend,
         ])
         .setup(|app| {
-            // Start Python backend on app startup
-            let python_process = app.state::<PythonProcess>();
-            tauri::async_runtime::block_on(async {
-                match start_backend(python_process).await {
-                    Ok(_) => println!("Backend started successfully"),
-                    Err(e) => eprintln!("Failed to start backend: {}", e),
+            // Start an initial session on app startup.
+            let app_handle = app.handle();
+            let sessions = app.state::<SessionManager>();
+            let (id, handle) = sessions.create();
+            let started = tauri::async_runtime::block_on(handle.start());
+            match started {
+                Ok(_) => {
+                    println!("Backend started successfully");
+                    launch_session(&app_handle, id, handle);
+                }
+                Err(e) => {
+                    sessions.remove(id);
+                    eprintln!("Failed to start backend: {}", e);
                 }
-            });
+            }
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -1,131 +1,544 @@
-use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
-use std::io::{BufRead, BufReader};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{broadcast, oneshot, Notify};
+
+use crate::error::BackendError;
+
+/// How long `send_command` waits for a correlated response before giving up.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the supervisor health-pings the backend.
+const HEALTH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a health `ping` may take before the backend is considered dead.
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Backoff bounds for automatic respawn after a crash.
+const BACKOFF_INITIAL: Duration = Duration::from_millis(250);
+const BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// `CREATE_NO_WINDOW`: keep the interpreter from flashing a console window on
+/// Windows, where the app itself runs under the `windows` subsystem.
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Apply the platform-specific spawn tweaks every backend process needs.
+#[cfg_attr(not(windows), allow(unused_variables))]
+pub(crate) fn configure_command(command: &mut Command) {
+    #[cfg(windows)]
+    command.creation_flags(CREATE_NO_WINDOW);
+}
+
+/// Capacity of the merged-output broadcast channel. Slow subscribers lose the
+/// oldest lines rather than stalling the reader.
+const OUTPUT_CHANNEL_CAPACITY: usize = 256;
+
+/// Map of in-flight request ids to the caller waiting for their response.
+type PendingMap = Arc<Mutex<HashMap<u64, oneshot::Sender<PythonResponse>>>>;
 
 #[derive(Debug)]
 pub struct PythonBackend {
     process: Mutex<Option<Child>>,
+    stdin: tokio::sync::Mutex<Option<ChildStdin>>,
+    pending: PendingMap,
+    output_tx: broadcast::Sender<String>,
+    status_tx: broadcast::Sender<BackendStatus>,
+    stream_tx: broadcast::Sender<StreamFrame>,
+    next_id: AtomicU64,
+    /// Bumped on every `start`/`restart` so a dead child's reader can tell it is
+    /// stale and must not clear the current generation's pending waiters.
+    generation: Arc<AtomicU64>,
     ready: Mutex<bool>,
+    /// Exit status of the most recently observed child death, so a command
+    /// issued against a crashed backend can report `ExitStatus` rather than a
+    /// vague `NotRunning`.
+    last_exit: Mutex<Option<std::process::ExitStatus>>,
+    /// Set once the session is stopped on purpose so the supervisor knows to
+    /// end rather than keep respawning.
+    stopped: Mutex<bool>,
+    /// Wakes the supervisor out of its sleep so a deliberate stop ends the task
+    /// promptly instead of on the next health interval.
+    shutdown: Notify,
+}
+
+/// Lifecycle state the supervisor broadcasts so the frontend can show, e.g., a
+/// "reconnecting" banner while the backend is being respawned.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum BackendStatus {
+    /// The backend process was (re)spawned; sent by every successful `start`,
+    /// before any health ping has been answered.
+    Running,
+    /// The backend died and is being respawned; `attempt` counts from 1.
+    Reconnecting { attempt: u32 },
+    /// The backend was stopped on purpose and will not be respawned.
+    Stopped,
+}
+
+/// Kind of frame the backend emits for a given request id. Intermediate
+/// (`chunk`/`progress`) frames stream to the webview; only `final` resolves the
+/// awaiting `send_command`. Frames without a `kind` are treated as `final` so
+/// the plain request→response path is unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameKind {
+    Chunk,
+    Progress,
+    #[default]
+    Final,
+}
+
+/// A response frame as produced by the Python backend. The `id` mirrors the id
+/// of the originating request so the reader can route it to the right caller.
+#[derive(Debug, Deserialize)]
+struct ResponseFrame {
+    id: u64,
+    #[serde(default)]
+    kind: FrameKind,
+    #[serde(flatten)]
+    response: PythonResponse,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// An intermediate frame forwarded to the webview for a streaming job.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamFrame {
+    pub id: u64,
+    pub kind: FrameKind,
+    pub data: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PythonResponse {
-    status: String,
+    // Optional so an intermediate `chunk`/`progress` frame — which carries only
+    // `data` — still deserializes as a `ResponseFrame` and reaches the stream
+    // instead of being mistaken for a non-JSON log line.
+    status: Option<String>,
     data: Option<Value>,
     error: Option<String>,
 }
 
+impl Default for PythonBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PythonBackend {
     pub fn new() -> Self {
+        let (output_tx, _) = broadcast::channel(OUTPUT_CHANNEL_CAPACITY);
+        let (status_tx, _) = broadcast::channel(OUTPUT_CHANNEL_CAPACITY);
+        let (stream_tx, _) = broadcast::channel(OUTPUT_CHANNEL_CAPACITY);
         PythonBackend {
             process: Mutex::new(None),
+            stdin: tokio::sync::Mutex::new(None),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            output_tx,
+            status_tx,
+            stream_tx,
+            next_id: AtomicU64::new(1),
+            generation: Arc::new(AtomicU64::new(0)),
             ready: Mutex::new(false),
+            last_exit: Mutex::new(None),
+            stopped: Mutex::new(false),
+            shutdown: Notify::new(),
         }
     }
 
-    pub fn start(&self) -> Result<(), String> {
-        let mut process_guard = self.process.lock().unwrap();
-        
-        if process_guard.is_some() {
-            return Ok(());
+    pub async fn start(&self) -> Result<(), BackendError> {
+        {
+            let process_guard = self.process.lock().unwrap();
+            if process_guard.is_some() {
+                return Ok(());
+            }
         }
 
-        let python_process = Command::new("python")
-            .args(&["-m", "backend.main"])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to start Python backend: {}", e))?;
-
-        // Start stdout reading thread
-        if let Some(stdout) = python_process.stdout.clone() {
-            std::thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines() {
-                    if let Ok(line) = line {
-                        println!("Python stdout: {}", line);
-                    }
-                }
-            });
-        }
+        let mut command = Command::new("python");
+        command
+            .args(["-m", "backend.main"])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+        configure_command(&mut command);
+        let mut python_process = command.spawn().map_err(BackendError::from_spawn)?;
 
-        // Start stderr reading thread
-        if let Some(stderr) = python_process.stderr.clone() {
-            std::thread::spawn(move || {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines() {
-                    if let Ok(line) = line {
-                        eprintln!("Python stderr: {}", line);
-                    }
+        let stdin = python_process
+            .stdin
+            .take()
+            .ok_or(BackendError::NotRunning)?;
+        let stdout = python_process
+            .stdout
+            .take()
+            .ok_or(BackendError::NotRunning)?;
+        let stderr = python_process
+            .stderr
+            .take()
+            .ok_or(BackendError::NotRunning)?;
+
+        // One task drains both pipes with a `select!` loop so a full stderr
+        // buffer can never stall stdout (or vice versa) and deadlock the child.
+        let pending = Arc::clone(&self.pending);
+        let output_tx = self.output_tx.clone();
+        let stream_tx = self.stream_tx.clone();
+        let generation = Arc::clone(&self.generation);
+        let my_generation = generation.fetch_add(1, Ordering::SeqCst) + 1;
+        tokio::spawn(async move {
+            let mut out_lines = BufReader::new(stdout).lines();
+            let mut err_lines = BufReader::new(stderr).lines();
+            let mut out_open = true;
+            let mut err_open = true;
+
+            while out_open || err_open {
+                tokio::select! {
+                    line = out_lines.next_line(), if out_open => match line {
+                        Ok(Some(line)) => Self::route_stdout(&pending, &output_tx, &stream_tx, line),
+                        _ => out_open = false,
+                    },
+                    line = err_lines.next_line(), if err_open => match line {
+                        Ok(Some(line)) => {
+                            eprintln!("Python stderr: {}", line);
+                            let _ = output_tx.send(line);
+                        }
+                        _ => err_open = false,
+                    },
                 }
-            });
-        }
+            }
 
-        *process_guard = Some(python_process);
+            // The backend closed its pipes (EOF): drop every pending sender so
+            // the waiting callers resolve to `NotRunning` instead of hanging.
+            // Only do so if we are still the current generation — after a
+            // restart, a newer child owns `pending` and this stale reader must
+            // not wipe its in-flight waiters.
+            if generation.load(Ordering::SeqCst) == my_generation {
+                pending.lock().unwrap().clear();
+            }
+        });
+
+        *self.stdin.lock().await = Some(stdin);
+        *self.process.lock().unwrap() = Some(python_process);
         *self.ready.lock().unwrap() = true;
+        *self.last_exit.lock().unwrap() = None;
+        let _ = self.status_tx.send(BackendStatus::Running);
 
         Ok(())
     }
 
-    pub fn stop(&self) -> Result<(), String> {
-        let mut process_guard = self.process.lock().unwrap();
-        
-        if let Some(mut process) = process_guard.take() {
-            process.kill()
-                .map_err(|e| format!("Failed to stop Python backend: {}", e))?;
-            process.wait()
-                .map_err(|e| format!("Failed to wait for Python backend to stop: {}", e))?;
+    /// Route a single stdout line. A `final` frame resolves the waiting caller
+    /// (or, for a fire-and-forget streaming job, is forwarded on the stream);
+    /// `chunk`/`progress` frames are always forwarded to the webview. Anything
+    /// that isn't a frame is log output mirrored onto the merged stream.
+    fn route_stdout(
+        pending: &PendingMap,
+        output_tx: &broadcast::Sender<String>,
+        stream_tx: &broadcast::Sender<StreamFrame>,
+        line: String,
+    ) {
+        match serde_json::from_str::<ResponseFrame>(&line) {
+            Ok(frame) if frame.kind == FrameKind::Final => {
+                match pending.lock().unwrap().remove(&frame.id) {
+                    Some(tx) => {
+                        let _ = tx.send(frame.response);
+                    }
+                    None => {
+                        let _ = stream_tx.send(StreamFrame {
+                            id: frame.id,
+                            kind: frame.kind,
+                            data: frame.response.data,
+                        });
+                    }
+                }
+            }
+            Ok(frame) => {
+                let _ = stream_tx.send(StreamFrame {
+                    id: frame.id,
+                    kind: frame.kind,
+                    data: frame.response.data,
+                });
+            }
+            Err(_) => {
+                println!("Python stdout: {}", line);
+                let _ = output_tx.send(line);
+            }
+        }
+    }
+
+    pub async fn stop(&self) -> Result<(), BackendError> {
+        let child = self.process.lock().unwrap().take();
+        if let Some(mut child) = child {
+            child.kill().await.map_err(BackendError::SpawnFailed)?;
         }
 
+        *self.stdin.lock().await = None;
         *self.ready.lock().unwrap() = false;
+        self.signal_shutdown();
+        self.fail_pending();
+        let _ = self.status_tx.send(BackendStatus::Stopped);
         Ok(())
     }
 
-    pub fn send_command(&self, command: &str, args: Value) -> Result<PythonResponse, String> {
-        if !*self.ready.lock().unwrap() {
-            return Err("Python backend is not ready".to_string());
+    /// Mark the session as deliberately stopped and wake the supervisor so it
+    /// ends. Idempotent, and safe to call before the graceful `stop` kill.
+    pub fn signal_shutdown(&self) {
+        *self.stopped.lock().unwrap() = true;
+        self.shutdown.notify_one();
+    }
+
+    fn is_stopped(&self) -> bool {
+        *self.stopped.lock().unwrap()
+    }
+
+    pub async fn send_command(
+        &self,
+        command: &str,
+        args: Value,
+    ) -> Result<PythonResponse, BackendError> {
+        self.request(command, args, RESPONSE_TIMEOUT).await
+    }
+
+    /// Issue a correlated request and wait up to `timeout` for its response.
+    async fn request(
+        &self,
+        command: &str,
+        args: Value,
+        timeout: Duration,
+    ) -> Result<PythonResponse, BackendError> {
+        if !self.is_ready() {
+            return Err(self.unavailable_error());
         }
 
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
         let request = json!({
+            "id": id,
             "command": command,
-            "args": args
+            "args": args,
         });
 
-        let process_guard = self.process.lock().unwrap();
-        if let Some(process) = &*process_guard {
-            if let Some(stdin) = process.stdin.as_ref() {
-                // Send command to Python process
-                serde_json::to_writer(stdin, &request)
-                    .map_err(|e| format!("Failed to send command: {}", e))?;
-                
-                // TODO: Implement proper response reading
-                // For now, return a dummy response
-                Ok(PythonResponse {
-                    status: "success".to_string(),
-                    data: Some(json!({"result": "Command sent successfully"})),
-                    error: None,
-                })
-            } else {
-                Err("Failed to get stdin of Python process".to_string())
+        // Register our interest before writing so we can never miss a response
+        // that races back ahead of us.
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+
+        if let Err(e) = self.write_request(&request).await {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(e);
+        }
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(response)) => Ok(response),
+            // Sender dropped without sending: the backend went away. If it
+            // crashed with a nonzero status, report that instead.
+            Ok(Err(_)) => Err(self.unavailable_error()),
+            Err(_) => {
+                self.pending.lock().unwrap().remove(&id);
+                Err(BackendError::Timeout)
             }
-        } else {
-            Err("Python process is not running".to_string())
         }
     }
 
+    /// Drop every in-flight request so callers resolve to `NotRunning` instead
+    /// of blocking forever once the backend has gone away.
+    fn fail_pending(&self) {
+        self.pending.lock().unwrap().clear();
+    }
+
+    /// Synchronously kill the child and mark the backend stopped. Used from
+    /// `Drop` paths where awaiting `stop` isn't possible.
+    pub fn shutdown(&self) {
+        *self.ready.lock().unwrap() = false;
+        self.signal_shutdown();
+        if let Some(mut child) = self.process.lock().unwrap().take() {
+            let _ = child.start_kill();
+        }
+        self.fail_pending();
+    }
+
+    async fn write_request(&self, request: &Value) -> Result<(), BackendError> {
+        let mut stdin_guard = self.stdin.lock().await;
+        let stdin = stdin_guard.as_mut().ok_or(BackendError::NotRunning)?;
+        let mut line = request.to_string();
+        line.push('\n');
+        // A broken pipe here means the child is gone rather than a spawn fault.
+        stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|_| BackendError::NotRunning)?;
+        stdin
+            .flush()
+            .await
+            .map_err(|_| BackendError::NotRunning)
+    }
+
+    /// Subscribe to the merged stdout/stderr log stream of the backend.
+    pub fn subscribe_output(&self) -> broadcast::Receiver<String> {
+        self.output_tx.subscribe()
+    }
+
+    /// Subscribe to backend lifecycle transitions (running / reconnecting).
+    pub fn subscribe_status(&self) -> broadcast::Receiver<BackendStatus> {
+        self.status_tx.subscribe()
+    }
+
+    /// Re-broadcast the current `Running` status. The first `Running` fired
+    /// inside `start` is lost because listeners are wired up afterwards, so
+    /// callers replay it once their subscriber is in place.
+    pub fn announce_running(&self) {
+        if self.is_ready() {
+            let _ = self.status_tx.send(BackendStatus::Running);
+        }
+    }
+
+    /// Subscribe to intermediate stream frames emitted by streaming jobs.
+    pub fn subscribe_stream(&self) -> broadcast::Receiver<StreamFrame> {
+        self.stream_tx.subscribe()
+    }
+
+    /// Start a streaming job without awaiting its result. Intermediate and
+    /// final frames for the returned id arrive on the stream channel; use
+    /// [`cancel`](Self::cancel) to abort it.
+    pub async fn subscribe(&self, command: &str, args: Value) -> Result<u64, BackendError> {
+        if !self.is_ready() {
+            return Err(BackendError::NotRunning);
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.write_request(&json!({
+            "id": id,
+            "command": command,
+            "args": args,
+        }))
+        .await?;
+        Ok(id)
+    }
+
+    /// Abort a streaming job by writing a `cancel` message for its id.
+    pub async fn cancel(&self, id: u64) -> Result<(), BackendError> {
+        // Drop any waiter so a later `final` frame doesn't resolve a stale call.
+        self.pending.lock().unwrap().remove(&id);
+        self.write_request(&json!({
+            "id": id,
+            "command": "cancel",
+        }))
+        .await
+    }
+
     pub fn is_ready(&self) -> bool {
         *self.ready.lock().unwrap()
     }
+
+    /// Health-ping the backend, expecting a `pong` within `PING_TIMEOUT`.
+    async fn ping(&self) -> bool {
+        matches!(
+            self.request("ping", json!({}), PING_TIMEOUT).await,
+            Ok(response) if response.status.as_deref() == Some("pong")
+        )
+    }
+
+    /// Non-blocking check for an unexpected child exit via `try_wait`. A
+    /// reaped exit status is stashed in `last_exit` so callers can report the
+    /// crash as `ExitStatus`.
+    fn has_exited(&self) -> bool {
+        let mut guard = self.process.lock().unwrap();
+        match guard.as_mut() {
+            Some(child) => match child.try_wait() {
+                Ok(Some(status)) => {
+                    *self.last_exit.lock().unwrap() = Some(status);
+                    true
+                }
+                Ok(None) => false,
+                Err(_) => true,
+            },
+            None => true,
+        }
+    }
+
+    /// Classify why the backend is unavailable: a recorded nonzero exit surfaces
+    /// as `ExitStatus`, otherwise `NotRunning`.
+    fn unavailable_error(&self) -> BackendError {
+        match *self.last_exit.lock().unwrap() {
+            Some(status) if !status.success() => BackendError::ExitStatus(status),
+            _ => BackendError::NotRunning,
+        }
+    }
+
+    /// Tear down the current (dead) child and spawn a fresh one.
+    async fn restart(&self) -> Result<(), BackendError> {
+        {
+            let mut guard = self.process.lock().unwrap();
+            if let Some(mut child) = guard.take() {
+                let _ = child.start_kill();
+            }
+        }
+        *self.stdin.lock().await = None;
+        *self.ready.lock().unwrap() = false;
+        self.fail_pending();
+        self.start().await
+    }
+
+    /// Run the supervisor loop: health-ping the backend on an interval, and on
+    /// a detected death respawn it with exponential backoff, broadcasting
+    /// status transitions so the frontend can surface "reconnecting".
+    pub async fn supervise(self: Arc<Self>) {
+        let mut backoff = BACKOFF_INITIAL;
+        loop {
+            // Wait a health interval, but wake early for a deliberate stop so
+            // the task ends (and the last `Arc` drops) instead of spinning.
+            tokio::select! {
+                _ = tokio::time::sleep(HEALTH_INTERVAL) => {}
+                _ = self.shutdown.notified() => return,
+            }
+            if self.is_stopped() {
+                return;
+            }
+
+            // Skip while the backend isn't up (e.g. mid-restart).
+            if !self.is_ready() {
+                continue;
+            }
+
+            if !self.has_exited() && self.ping().await {
+                backoff = BACKOFF_INITIAL;
+                continue;
+            }
+
+            // The backend is gone: fail any pending callers and respawn.
+            self.fail_pending();
+            let mut attempt = 0;
+            loop {
+                if self.is_stopped() {
+                    return;
+                }
+                attempt += 1;
+                let _ = self.status_tx.send(BackendStatus::Reconnecting { attempt });
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = self.shutdown.notified() => return,
+                }
+                if self.is_stopped() {
+                    return;
+                }
+                match self.restart().await {
+                    Ok(()) if self.ping().await => {
+                        backoff = BACKOFF_INITIAL;
+                        break;
+                    }
+                    _ => backoff = (backoff * 2).min(BACKOFF_MAX),
+                }
+            }
+        }
+    }
 }
 
 // Implement Drop to ensure Python process is cleaned up
 impl Drop for PythonBackend {
     fn drop(&mut self) {
-        if let Err(e) = self.stop() {
-            eprintln!("Error stopping Python backend: {}", e);
+        if let Some(mut child) = self.process.lock().unwrap().take() {
+            // Drop runs in a sync context, so request the kill without awaiting.
+            let _ = child.start_kill();
         }
     }
 }
@@ -134,20 +547,119 @@ impl Drop for PythonBackend {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_python_backend_lifecycle() {
+    #[tokio::test]
+    async fn test_python_backend_lifecycle() {
         let backend = PythonBackend::new();
-        
+
         // Test starting
-        assert!(backend.start().is_ok());
+        assert!(backend.start().await.is_ok());
         assert!(backend.is_ready());
-        
-        // Test sending command
-        let result = backend.send_command("test", json!({}));
-        assert!(result.is_ok());
-        
+
         // Test stopping
-        assert!(backend.stop().is_ok());
+        assert!(backend.stop().await.is_ok());
         assert!(!backend.is_ready());
     }
-}
\ No newline at end of file
+
+    /// Build an empty pending map plus the two broadcast senders `route_stdout`
+    /// needs, so the demux can be exercised without a real interpreter.
+    fn demux_fixture() -> (
+        PendingMap,
+        broadcast::Sender<String>,
+        broadcast::Sender<StreamFrame>,
+    ) {
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let (output_tx, _) = broadcast::channel(16);
+        let (stream_tx, _) = broadcast::channel(16);
+        (pending, output_tx, stream_tx)
+    }
+
+    #[tokio::test]
+    async fn test_route_stdout_demuxes_final_to_matching_waiter() {
+        let (pending, output_tx, stream_tx) = demux_fixture();
+        let (tx1, rx1) = oneshot::channel();
+        let (tx2, mut rx2) = oneshot::channel();
+        pending.lock().unwrap().insert(1, tx1);
+        pending.lock().unwrap().insert(2, tx2);
+
+        PythonBackend::route_stdout(
+            &pending,
+            &output_tx,
+            &stream_tx,
+            r#"{"id":1,"status":"ok","data":"hello"}"#.to_string(),
+        );
+
+        // Only waiter 1 is resolved; waiter 2 stays pending and untouched.
+        let response = rx1.await.expect("waiter 1 should be resolved");
+        assert_eq!(response.status.as_deref(), Some("ok"));
+        assert!(rx2.try_recv().is_err());
+        assert!(pending.lock().unwrap().contains_key(&2));
+    }
+
+    #[tokio::test]
+    async fn test_route_stdout_streams_chunk_without_status() {
+        let (pending, output_tx, stream_tx) = demux_fixture();
+        let mut stream_rx = stream_tx.subscribe();
+
+        // A chunk frame carries no `status`; it must still reach the stream.
+        PythonBackend::route_stdout(
+            &pending,
+            &output_tx,
+            &stream_tx,
+            r#"{"id":5,"kind":"chunk","data":"token"}"#.to_string(),
+        );
+
+        let frame = stream_rx.try_recv().expect("chunk should be forwarded");
+        assert_eq!(frame.id, 5);
+        assert_eq!(frame.kind, FrameKind::Chunk);
+        assert_eq!(frame.data.as_ref().and_then(|v| v.as_str()), Some("token"));
+    }
+
+    #[tokio::test]
+    async fn test_route_stdout_treats_non_json_as_log() {
+        let (pending, output_tx, stream_tx) = demux_fixture();
+        let mut output_rx = output_tx.subscribe();
+        let (tx, mut rx) = oneshot::channel();
+        pending.lock().unwrap().insert(1, tx);
+
+        PythonBackend::route_stdout(
+            &pending,
+            &output_tx,
+            &stream_tx,
+            "loaded model weights".to_string(),
+        );
+
+        // Log lines go to the output stream and never touch the waiter.
+        assert_eq!(output_rx.try_recv().unwrap(), "loaded model weights");
+        assert!(rx.try_recv().is_err());
+        assert!(pending.lock().unwrap().contains_key(&1));
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_id_leaves_waiter_to_time_out() {
+        let (pending, output_tx, stream_tx) = demux_fixture();
+        let (tx, rx) = oneshot::channel::<PythonResponse>();
+        pending.lock().unwrap().insert(1, tx);
+
+        // A final frame for an unrelated id must not resolve our waiter.
+        PythonBackend::route_stdout(
+            &pending,
+            &output_tx,
+            &stream_tx,
+            r#"{"id":99,"status":"ok"}"#.to_string(),
+        );
+
+        let timed_out = tokio::time::timeout(Duration::from_millis(20), rx).await;
+        assert!(timed_out.is_err(), "waiter should still be unresolved");
+    }
+
+    #[tokio::test]
+    async fn test_request_on_unstarted_backend_errors_fast() {
+        let backend = PythonBackend::new();
+        // Never started: a command resolves immediately to NotRunning rather
+        // than waiting out the response timeout.
+        let result = backend
+            .request("noop", json!({}), Duration::from_secs(30))
+            .await;
+        assert!(matches!(result, Err(BackendError::NotRunning)));
+    }
+}
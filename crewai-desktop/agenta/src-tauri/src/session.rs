@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::python::PythonBackend;
+
+/// An owned, reference-counted backend. Each handle has its own child process,
+/// stdin writer, and response-correlation map, so sessions are fully isolated.
+pub type BackendHandle = Arc<PythonBackend>;
+
+/// Opaque identifier handed back to the frontend when a session is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionId(u64);
+
+impl std::fmt::Display for SessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "session-{}", self.0)
+    }
+}
+
+/// Tracks every live backend session so the app can run more than one
+/// isolated Python agent at a time.
+#[derive(Default)]
+pub struct SessionManager {
+    sessions: Mutex<HashMap<SessionId, BackendHandle>>,
+    next_id: AtomicU64,
+}
+
+impl SessionManager {
+    pub fn new() -> Self {
+        SessionManager::default()
+    }
+
+    /// Allocate a fresh session id and register a new (not-yet-started) handle.
+    pub fn create(&self) -> (SessionId, BackendHandle) {
+        let id = SessionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let handle: BackendHandle = Arc::new(PythonBackend::new());
+        self.sessions.lock().unwrap().insert(id, Arc::clone(&handle));
+        (id, handle)
+    }
+
+    /// Look up a live session's handle.
+    pub fn get(&self, id: SessionId) -> Option<BackendHandle> {
+        self.sessions.lock().unwrap().get(&id).cloned()
+    }
+
+    /// Remove a session from the manager, returning its handle if present.
+    ///
+    /// Signals the backend to shut down so its supervisor and output forwarder
+    /// tasks end: dropping the handle from the map alone isn't enough, because
+    /// the supervisor holds its own `Arc` and the forwarders only stop once the
+    /// backend (and its broadcast senders) are dropped.
+    pub fn remove(&self, id: SessionId) -> Option<BackendHandle> {
+        let handle = self.sessions.lock().unwrap().remove(&id);
+        if let Some(handle) = &handle {
+            handle.signal_shutdown();
+        }
+        handle
+    }
+}
+
+// Tear down every session's child process when the manager goes away.
+impl Drop for SessionManager {
+    fn drop(&mut self) {
+        for (_, handle) in self.sessions.lock().unwrap().drain() {
+            handle.shutdown();
+        }
+    }
+}
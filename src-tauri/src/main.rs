@@ -6,6 +6,21 @@
 use std::process::Command;
 use std::thread;
 
+/// `CREATE_NO_WINDOW`: keep the interpreter from flashing a console window on
+/// Windows, where the app itself runs under the `windows` subsystem.
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Apply the platform-specific spawn tweaks every backend process needs.
+#[cfg_attr(not(windows), allow(unused_variables))]
+fn configure_command(command: &mut Command) {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+}
+
 // Start the Python backend server
 fn start_backend() {
     thread::spawn(|| {
@@ -15,10 +30,10 @@ fn start_backend() {
             "python3"
         };
 
-        Command::new(python_command)
-            .args(&["src/backend/main.py"])
-            .spawn()
-            .expect("Failed to start backend server");
+        let mut command = Command::new(python_command);
+        command.args(&["src/backend/main.py"]);
+        configure_command(&mut command);
+        command.spawn().expect("Failed to start backend server");
     });
 }
 